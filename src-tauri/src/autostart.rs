@@ -0,0 +1,95 @@
+// Auto-start-on-boot toggle, backed by the `auto-launch` crate and persisted to disk
+// so the preference survives restarts.
+
+use auto_launch::{AutoLaunch, AutoLaunchBuilder};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+const PREF_FILE: &str = "autostart.conf";
+const APP_NAME: &str = "pupcidslittletiktokhelper";
+
+fn pref_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path_resolver()
+        .app_config_dir()
+        .ok_or_else(|| "failed to resolve app config dir".to_string())?;
+    if !dir.exists() {
+        let _ = fs::create_dir_all(&dir);
+    }
+    Ok(dir.join(PREF_FILE))
+}
+
+// On macOS, `current_exe()` resolves to the binary inside `AppName.app/Contents/MacOS/`.
+// The auto-launch crate's macOS backend registers a login item for the `.app` bundle, so
+// we need the bundle root, not the binary buried three directories inside it.
+#[cfg(target_os = "macos")]
+fn app_path(exe_path: PathBuf) -> PathBuf {
+    exe_path
+        .ancestors()
+        .nth(3)
+        .map(PathBuf::from)
+        .unwrap_or(exe_path)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn app_path(exe_path: PathBuf) -> PathBuf {
+    exe_path
+}
+
+fn auto_launch() -> Result<AutoLaunch, String> {
+    let exe_path = std::env::current_exe()
+        .map_err(|e| format!("failed to resolve current executable path: {}", e))?;
+    AutoLaunchBuilder::new()
+        .set_app_name(APP_NAME)
+        .set_app_path(&app_path(exe_path).to_string_lossy())
+        .build()
+        .map_err(|e| format!("failed to build AutoLaunch: {}", e))
+}
+
+// Reads the persisted preference, defaulting to `false` if nothing was saved yet or
+// if the preference file can't be located.
+pub fn is_enabled(app: &AppHandle) -> bool {
+    match pref_path(app) {
+        Ok(path) => fs::read_to_string(path)
+            .map(|s| s.trim() == "1")
+            .unwrap_or(false),
+        Err(e) => {
+            eprintln!("failed to read auto-start preference: {}", e);
+            false
+        }
+    }
+}
+
+fn persist(app: &AppHandle, enabled: bool) {
+    match pref_path(app) {
+        Ok(path) => {
+            let _ = fs::write(path, if enabled { "1" } else { "0" });
+        }
+        Err(e) => eprintln!("failed to persist auto-start preference: {}", e),
+    }
+}
+
+// Applies `enabled` to the OS login-item registration and persists the choice.
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let launcher = auto_launch()?;
+    let result = if enabled {
+        launcher.enable()
+    } else {
+        launcher.disable()
+    };
+    result.map_err(|e| format!("failed to update auto-start registration: {}", e))?;
+    persist(app, enabled);
+    Ok(())
+}
+
+// Reflects the current on/off state as a checkmark on the tray menu item.
+pub fn sync_tray_checkmark(app: &AppHandle, enabled: bool) {
+    if let Err(e) = app
+        .tray_handle()
+        .get_item("auto_start")
+        .set_selected(enabled)
+    {
+        eprintln!("failed to update auto-start tray checkmark: {}", e);
+    }
+}
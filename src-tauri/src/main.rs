@@ -3,46 +3,28 @@
     windows_subsystem = "windows"
 )]
 
+use std::sync::Mutex;
 use tauri::{
     AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
     SystemTrayMenuItem, WindowEvent,
 };
-use std::process::{Child, Command};
-use std::sync::Mutex;
-
-// Node.js server process
-struct NodeServer {
-    process: Mutex<Option<Child>>,
-}
 
-// Start Node.js server
-fn start_node_server() -> Result<Child, std::io::Error> {
-    #[cfg(target_os = "windows")]
-    let child = Command::new("node")
-        .arg("server.js")
-        .current_dir(".")
-        .spawn()?;
+mod autostart;
+mod server;
+mod updater;
 
-    #[cfg(not(target_os = "windows"))]
-    let child = Command::new("node")
-        .arg("server.js")
-        .current_dir(".")
-        .spawn()?;
-
-    Ok(child)
-}
+use server::NodeServer;
+use updater::UpdateState;
 
 // System tray menu
 fn create_tray_menu() -> SystemTrayMenu {
-    let show = CustomMenuItem::new("show".to_string(), "Show Window");
-    let hide = CustomMenuItem::new("hide".to_string(), "Hide Window");
+    let toggle_window = CustomMenuItem::new("toggle_window".to_string(), "Hide Window");
     let auto_start = CustomMenuItem::new("auto_start".to_string(), "Auto-Start on Boot");
     let check_update = CustomMenuItem::new("update".to_string(), "Check for Updates");
     let quit = CustomMenuItem::new("quit".to_string(), "Quit");
 
     SystemTrayMenu::new()
-        .add_item(show)
-        .add_item(hide)
+        .add_item(toggle_window)
         .add_native_item(SystemTrayMenuItem::Separator)
         .add_item(auto_start)
         .add_native_item(SystemTrayMenuItem::Separator)
@@ -51,55 +33,88 @@ fn create_tray_menu() -> SystemTrayMenu {
         .add_item(quit)
 }
 
+// Keeps the single toggle item's title in sync with the `main` window's actual
+// visibility, so the tray only ever offers the one relevant action.
+fn sync_window_toggle_item(app: &AppHandle) {
+    let Some(window) = app.get_window("main") else {
+        return;
+    };
+    let title = if window.is_visible().unwrap_or(true) {
+        "Hide Window"
+    } else {
+        "Show Window"
+    };
+    let _ = app.tray_handle().get_item("toggle_window").set_title(title);
+}
+
 // Tauri commands (callable from frontend)
 #[tauri::command]
 fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
-#[tauri::command]
-async fn check_for_updates(app: AppHandle) -> Result<(), String> {
-    let updater = app.updater();
-    match updater.check().await {
-        Ok(update_response) => {
-            if update_response.is_update_available() {
-                println!("Update available: {}", update_response.latest_version());
-                // Update dialog will be shown automatically if configured
-                Ok(())
-            } else {
-                println!("App is up to date");
-                Ok(())
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to check for updates: {}", e);
-            Err(format!("Update check failed: {}", e))
-        }
-    }
-}
-
 #[tauri::command]
 fn minimize_to_tray(window: tauri::Window) {
     window.hide().unwrap();
+    sync_window_toggle_item(&window.app_handle());
 }
 
 // Main entry point
 fn main() {
-    // Start Node.js server
-    let server_process = start_node_server().expect("Failed to start Node.js server");
-    let node_server = NodeServer {
-        process: Mutex::new(Some(server_process)),
-    };
-
-    // Wait for server to start
-    std::thread::sleep(std::time::Duration::from_secs(2));
-
     // Create system tray
     let tray = SystemTray::new().with_menu(create_tray_menu());
 
     tauri::Builder::default()
-        .manage(node_server)
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            // Another instance was launched; focus the existing window instead of
+            // letting the new process spawn a second Node.js server.
+            if let Some(window) = app.get_window("main") {
+                window.show().ok();
+                window.set_focus().ok();
+            }
+            sync_window_toggle_item(app);
+        }))
         .system_tray(tray)
+        .manage(UpdateState::default())
+        .setup(|app| {
+            // Keep the webview hidden until the backend is actually ready to serve it.
+            let window = app.get_window("main").unwrap();
+            window.hide().ok();
+
+            // Only the primary instance reaches here, so the Node.js server is
+            // guaranteed to be started exactly once.
+            let handle = app.handle();
+            server::emit_status(&handle, server::ServerStatus::Starting);
+            let server_process = server::start_node_server(&handle).unwrap_or_else(|e| {
+                tauri::api::dialog::blocking::message(
+                    None::<&tauri::Window>,
+                    "Failed to Start Server",
+                    &e,
+                );
+                std::process::exit(1);
+            });
+            app.manage(NodeServer {
+                process: Mutex::new(Some(server_process)),
+            });
+
+            if let Err(e) = server::wait_until_ready(std::time::Duration::from_secs(15)) {
+                eprintln!("{}", e);
+                tauri::api::dialog::blocking::message(
+                    None::<&tauri::Window>,
+                    "Failed to Start Server",
+                    &e,
+                );
+                std::process::exit(1);
+            }
+            server::emit_status(&handle, server::ServerStatus::Running);
+            window.show().ok();
+            window.set_focus().ok();
+
+            server::spawn_supervisor(handle.clone());
+            autostart::sync_tray_checkmark(&handle, autostart::is_enabled(&handle));
+            sync_window_toggle_item(&handle);
+            Ok(())
+        })
         .on_system_tray_event(|app, event| match event {
             SystemTrayEvent::LeftClick {
                 position: _,
@@ -110,30 +125,35 @@ fn main() {
                 let window = app.get_window("main").unwrap();
                 window.show().unwrap();
                 window.set_focus().unwrap();
+                sync_window_toggle_item(app);
             }
             SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
-                "show" => {
-                    let window = app.get_window("main").unwrap();
-                    window.show().unwrap();
-                    window.set_focus().unwrap();
-                }
-                "hide" => {
+                "toggle_window" => {
                     let window = app.get_window("main").unwrap();
-                    window.hide().unwrap();
+                    if window.is_visible().unwrap_or(true) {
+                        window.hide().unwrap();
+                    } else {
+                        window.show().unwrap();
+                        window.set_focus().unwrap();
+                    }
+                    sync_window_toggle_item(app);
                 }
                 "auto_start" => {
-                    // TODO: Implement auto-start toggle
-                    println!("Auto-start toggled");
+                    let enabled = !autostart::is_enabled(app);
+                    match autostart::set_enabled(app, enabled) {
+                        Ok(()) => autostart::sync_tray_checkmark(app, enabled),
+                        Err(e) => eprintln!("failed to toggle auto-start: {}", e),
+                    }
                 }
                 "update" => {
-                    // Trigger update check
+                    // Same command the frontend's "Check for Updates" button calls.
                     tauri::async_runtime::spawn(async move {
                         let app_handle = app.app_handle();
-                        check_for_updates(app_handle).await.ok();
+                        updater::check_for_updates(app_handle).await.ok();
                     });
                 }
                 "quit" => {
-                    // Clean shutdown
+                    server::shutdown_gracefully(&app.state::<NodeServer>());
                     std::process::exit(0);
                 }
                 _ => {}
@@ -145,24 +165,23 @@ fn main() {
                 // Prevent close, hide instead
                 event.window().hide().unwrap();
                 api.prevent_close();
+                sync_window_toggle_item(&event.window().app_handle());
             }
         })
         .invoke_handler(tauri::generate_handler![
             get_app_version,
-            check_for_updates,
-            minimize_to_tray
+            minimize_to_tray,
+            updater::check_for_updates,
+            updater::install_update,
+            updater::get_update_status
         ])
         .build(tauri::generate_context!())
         .expect("Error while building Tauri application")
         .run(|app_handle, event| {
             if let tauri::RunEvent::ExitRequested { api, .. } = event {
-                // Kill Node.js server on exit
-                let node_server = app_handle.state::<NodeServer>();
-                if let Ok(mut process) = node_server.process.lock() {
-                    if let Some(mut child) = process.take() {
-                        child.kill().ok();
-                    }
-                }
+                // Give the Node.js server a chance to shut down cleanly before
+                // falling back to a hard kill.
+                server::shutdown_gracefully(&app_handle.state::<NodeServer>());
                 api.prevent_exit();
             }
         });
@@ -0,0 +1,256 @@
+// Node.js sidecar process: binary/script resolution, spawning, and crash supervision.
+
+use serde::Serialize;
+use std::io::Write;
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+pub struct NodeServer {
+    pub process: Mutex<Option<Child>>,
+}
+
+// The port server.js listens on; used for both the startup readiness probe and the
+// non-Unix graceful shutdown request.
+pub const NODE_SERVER_PORT: u16 = 3000;
+
+// Common install locations to fall back to when `node` isn't on PATH, e.g. when the
+// app is launched from a Finder/Explorer shortcut that doesn't inherit a shell PATH.
+#[cfg(target_os = "windows")]
+const COMMON_NODE_LOCATIONS: &[&str] = &[
+    r"C:\Program Files\nodejs\node.exe",
+    r"C:\Program Files (x86)\nodejs\node.exe",
+];
+#[cfg(target_os = "macos")]
+const COMMON_NODE_LOCATIONS: &[&str] = &[
+    "/usr/local/bin/node",
+    "/opt/homebrew/bin/node",
+    "/usr/bin/node",
+];
+#[cfg(all(unix, not(target_os = "macos")))]
+const COMMON_NODE_LOCATIONS: &[&str] = &["/usr/local/bin/node", "/usr/bin/node"];
+
+#[cfg(target_os = "windows")]
+const NODE_EXE_NAME: &str = "node.exe";
+#[cfg(not(target_os = "windows"))]
+const NODE_EXE_NAME: &str = "node";
+
+// Resolves the `node` executable to run, in order of preference:
+// 1. An explicit override via the `NODE_BINARY_PATH` environment variable.
+// 2. A lookup on PATH.
+// 3. A short list of common per-OS install locations.
+fn resolve_node_binary() -> Result<PathBuf, String> {
+    if let Ok(override_path) = std::env::var("NODE_BINARY_PATH") {
+        let path = PathBuf::from(override_path);
+        if path.is_file() {
+            return Ok(path);
+        }
+        return Err(format!(
+            "NODE_BINARY_PATH is set to '{}' but no file exists there",
+            path.display()
+        ));
+    }
+
+    if let Some(path) = std::env::var_os("PATH").and_then(|path_var| {
+        std::env::split_paths(&path_var)
+            .map(|dir| dir.join(NODE_EXE_NAME))
+            .find(|candidate| candidate.is_file())
+    }) {
+        return Ok(path);
+    }
+
+    if let Some(path) = COMMON_NODE_LOCATIONS
+        .iter()
+        .map(PathBuf::from)
+        .find(|candidate| candidate.is_file())
+    {
+        return Ok(path);
+    }
+
+    Err(
+        "Could not find a Node.js installation. Set NODE_BINARY_PATH to the node executable, \
+         or install Node.js and ensure it is on PATH."
+            .to_string(),
+    )
+}
+
+// Resolves the bundled `server.js` resource, which ships alongside the app in its
+// resource directory rather than relying on the process's working directory.
+fn resolve_server_script(app: &AppHandle) -> Result<PathBuf, String> {
+    let resource_dir = app
+        .path_resolver()
+        .resource_dir()
+        .ok_or_else(|| "Could not resolve the app's resource directory".to_string())?;
+    let script = resource_dir.join("server.js");
+    if !script.is_file() {
+        return Err(format!(
+            "Bundled server.js not found at '{}'",
+            script.display()
+        ));
+    }
+    Ok(script)
+}
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_RESTARTS_PER_WINDOW: u32 = 5;
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Serialize)]
+#[serde(tag = "state")]
+pub enum ServerStatus {
+    Starting,
+    Running,
+    Crashed,
+    GivingUp,
+}
+
+// Start Node.js server
+pub fn start_node_server(app: &AppHandle) -> Result<Child, String> {
+    let node_bin = resolve_node_binary()?;
+    let script = resolve_server_script(app)?;
+    let working_dir: &Path = script
+        .parent()
+        .ok_or_else(|| "server.js has no parent directory".to_string())?;
+
+    Command::new(node_bin)
+        .arg(&script)
+        .current_dir(working_dir)
+        .spawn()
+        .map_err(|e| format!("Failed to start Node.js server: {}", e))
+}
+
+pub fn emit_status(app: &AppHandle, status: ServerStatus) {
+    let _ = app.emit_all("server-status", status);
+}
+
+// Watches the Node.js child process and restarts it with exponential backoff when
+// it exits unexpectedly, giving up once it crashes too many times in a short window.
+pub fn spawn_supervisor(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut restarts_in_window = 0u32;
+        let mut window_start = Instant::now();
+        let mut started_at = Instant::now();
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let exited = {
+                let state = app.state::<NodeServer>();
+                let mut guard = state.process.lock().unwrap();
+                match guard.as_mut() {
+                    Some(child) => matches!(child.try_wait(), Ok(Some(_))),
+                    None => false,
+                }
+            };
+
+            if !exited {
+                if started_at.elapsed() >= STABLE_UPTIME && backoff != INITIAL_BACKOFF {
+                    backoff = INITIAL_BACKOFF;
+                    restarts_in_window = 0;
+                    window_start = Instant::now();
+                }
+                continue;
+            }
+
+            emit_status(&app, ServerStatus::Crashed);
+
+            if window_start.elapsed() > RESTART_WINDOW {
+                restarts_in_window = 0;
+                window_start = Instant::now();
+            }
+            restarts_in_window += 1;
+
+            if restarts_in_window > MAX_RESTARTS_PER_WINDOW {
+                emit_status(&app, ServerStatus::GivingUp);
+                break;
+            }
+
+            std::thread::sleep(backoff);
+            emit_status(&app, ServerStatus::Starting);
+
+            match start_node_server(&app) {
+                Ok(child) => {
+                    let state = app.state::<NodeServer>();
+                    *state.process.lock().unwrap() = Some(child);
+                    started_at = Instant::now();
+                    backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+                    emit_status(&app, ServerStatus::Running);
+                }
+                Err(e) => {
+                    eprintln!("failed to restart Node.js server: {}", e);
+                    backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+                }
+            }
+        }
+    });
+}
+
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+// Polls the server's port until it accepts connections, instead of blindly sleeping
+// and hoping the server is up in time. Returns an error if `timeout` elapses first.
+pub fn wait_until_ready(timeout: Duration) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if TcpStream::connect(("127.0.0.1", NODE_SERVER_PORT)).is_ok() {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "Node.js server did not become ready on port {} within {:?}",
+                NODE_SERVER_PORT, timeout
+            ));
+        }
+        std::thread::sleep(READINESS_POLL_INTERVAL);
+    }
+}
+
+// Asks the Node.js server to stop on its own (SIGTERM on Unix, a raw HTTP shutdown
+// request elsewhere) and only falls back to `kill()` if it hasn't exited after a
+// short grace period, avoiding orphaned children and a port stuck in TIME_WAIT.
+pub fn shutdown_gracefully(node_server: &NodeServer) {
+    let mut guard = node_server.process.lock().unwrap();
+    let Some(mut child) = guard.take() else {
+        return;
+    };
+
+    request_graceful_stop(&child);
+
+    let deadline = Instant::now() + SHUTDOWN_GRACE_PERIOD;
+    while Instant::now() < deadline {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    child.kill().ok();
+}
+
+#[cfg(unix)]
+fn request_graceful_stop(child: &Child) {
+    // SAFETY: `child.id()` is a valid pid owned by this process; sending SIGTERM
+    // is a no-op if the process has already exited.
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn request_graceful_stop(_child: &Child) {
+    if let Ok(mut stream) = TcpStream::connect(("127.0.0.1", NODE_SERVER_PORT)) {
+        let request = format!(
+            "POST /shutdown HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            NODE_SERVER_PORT
+        );
+        let _ = stream.write_all(request.as_bytes());
+    }
+}
@@ -0,0 +1,140 @@
+// App update flow: checks, progress, and install, all driven through the same
+// commands whether triggered from the tray menu or the webview.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+#[derive(Clone, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct UpdateProgress {
+    pub downloaded: usize,
+    pub total: Option<u64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(tag = "status")]
+pub enum UpdateStatus {
+    Idle,
+    Checking,
+    UpToDate,
+    Available(UpdateInfo),
+    Downloading,
+    ReadyToRestart,
+    Error { message: String },
+}
+
+pub struct UpdateState(pub Mutex<UpdateStatus>);
+
+impl Default for UpdateState {
+    fn default() -> Self {
+        UpdateState(Mutex::new(UpdateStatus::Idle))
+    }
+}
+
+fn set_status(app: &AppHandle, status: UpdateStatus) {
+    *app.state::<UpdateState>().0.lock().unwrap() = status.clone();
+    match &status {
+        UpdateStatus::Available(info) => {
+            let _ = app.emit_all("update://available", info.clone());
+        }
+        UpdateStatus::Error { message } => {
+            let _ = app.emit_all("update://error", message.clone());
+        }
+        _ => {}
+    }
+}
+
+#[tauri::command]
+pub fn get_update_status(app: AppHandle) -> UpdateStatus {
+    app.state::<UpdateState>().0.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<(), String> {
+    set_status(&app, UpdateStatus::Checking);
+
+    let updater = app.updater();
+    match updater.check().await {
+        Ok(update_response) => {
+            if update_response.is_update_available() {
+                set_status(
+                    &app,
+                    UpdateStatus::Available(UpdateInfo {
+                        version: update_response.latest_version().to_string(),
+                        notes: update_response.body().map(|s| s.to_string()),
+                    }),
+                );
+            } else {
+                set_status(&app, UpdateStatus::UpToDate);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let message = format!("Update check failed: {}", e);
+            set_status(
+                &app,
+                UpdateStatus::Error {
+                    message: message.clone(),
+                },
+            );
+            Err(message)
+        }
+    }
+}
+
+// Checks for an update again and, if one is available, downloads and installs it,
+// emitting `update://progress` events as bytes come in so the webview can render a progress bar.
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    let updater = app.updater();
+    let update_response = updater
+        .check()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+
+    if !update_response.is_update_available() {
+        return Err("No update is available".to_string());
+    }
+
+    set_status(&app, UpdateStatus::Downloading);
+
+    let progress_app = app.clone();
+    let total_downloaded = Mutex::new(0usize);
+    let result = update_response
+        .download_and_install(
+            move |chunk_len, total| {
+                let downloaded = {
+                    let mut total_downloaded = total_downloaded.lock().unwrap();
+                    *total_downloaded += chunk_len;
+                    *total_downloaded
+                };
+                let _ = progress_app
+                    .emit_all("update://progress", UpdateProgress { downloaded, total });
+            },
+            || {},
+        )
+        .await;
+
+    match result {
+        Ok(()) => {
+            set_status(&app, UpdateStatus::ReadyToRestart);
+            Ok(())
+        }
+        Err(e) => {
+            let message = format!("Update install failed: {}", e);
+            set_status(
+                &app,
+                UpdateStatus::Error {
+                    message: message.clone(),
+                },
+            );
+            Err(message)
+        }
+    }
+}